@@ -0,0 +1,95 @@
+//! Escaping `Display`/`Debug` for arbitrary-byte OS paths.
+
+use core::fmt::{self, Write as _};
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(all(feature = "std", unix))]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(all(feature = "std", target_os = "wasi"))]
+use std::os::wasi::ffi::OsStrExt;
+
+/// A wrapper for displaying an arbitrary, possibly non-UTF-8, byte sequence,
+/// such as a filesystem path or the target of a `readlink`.
+///
+/// Unlike paths, which may contain any byte value on most platforms,
+/// `Display`/`Debug` output has to be valid Unicode, so this renders
+/// printable ASCII bytes (`0x20..=0x7e`) verbatim, the common control bytes
+/// `\t`, `\n`, and `\r` as their familiar escapes, and every other byte as
+/// `\xNN`. This is a no-alloc, no-panic alternative to a lossy UTF-8
+/// conversion.
+///
+/// `Debug` additionally escapes `"` and `\` (and wraps the result in
+/// quotes), so that the output is always unambiguous quoted-string syntax,
+/// the way `str`'s own `Debug` impl behaves; `Display` leaves them as-is.
+///
+/// # Examples
+///
+/// ```
+/// use rustix::path::BytePath;
+///
+/// assert_eq!(
+///     format!("{}", BytePath::new(b"abc\xffdef\n")),
+///     "abc\\xffdef\\n"
+/// );
+/// assert_eq!(
+///     format!("{:?}", BytePath::new(b"a\"b\\c")),
+///     "\"a\\\"b\\\\c\""
+/// );
+/// ```
+pub struct BytePath<'a>(&'a [u8]);
+
+impl<'a> BytePath<'a> {
+    /// Construct a new `BytePath` wrapping `bytes`.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl<'a> From<&'a [u8]> for BytePath<'a> {
+    #[inline]
+    fn from(bytes: &'a [u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> From<&'a OsStr> for BytePath<'a> {
+    #[inline]
+    fn from(os_str: &'a OsStr) -> Self {
+        Self::new(os_str.as_bytes())
+    }
+}
+
+/// Write `bytes` escaped per [`BytePath`]'s doc comment, additionally
+/// escaping `"` and `\` when `escape_quotes` is set, as `Debug` needs to so
+/// that its output is unambiguous quoted-string syntax.
+fn write_escaped(bytes: &[u8], escape_quotes: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for &byte in bytes {
+        match byte {
+            b'"' if escape_quotes => f.write_str("\\\"")?,
+            b'\\' if escape_quotes => f.write_str("\\\\")?,
+            0x20..=0x7e => f.write_char(byte as char)?,
+            b'\t' => f.write_str("\\t")?,
+            b'\n' => f.write_str("\\n")?,
+            b'\r' => f.write_str("\\r")?,
+            _ => write!(f, "\\x{byte:02x}")?,
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for BytePath<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_escaped(self.0, false, f)
+    }
+}
+
+impl fmt::Debug for BytePath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        write_escaped(self.0, true, f)?;
+        f.write_char('"')
+    }
+}
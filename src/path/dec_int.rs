@@ -2,14 +2,14 @@
 //!
 //! # Safety
 //!
-//! This uses `CStr::from_bytes_with_nul_unchecked` and
-//! `str::from_utf8_unchecked`on the buffer that it filled itself.
+//! This uses `CStr::from_bytes_with_nul_unchecked`,
+//! `str::from_utf8_unchecked`, `ptr::copy_nonoverlapping`, and
+//! `slice::from_raw_parts` on the buffer that it filled itself.
 #![allow(unsafe_code)]
 
 use crate::backend::fd::{AsFd, AsRawFd};
 use crate::ffi::CStr;
-use core::any::TypeId;
-use core::mem::{self, MaybeUninit};
+use core::mem::MaybeUninit;
 use itoa::{Buffer, Integer};
 #[cfg(all(feature = "std", unix))]
 use std::os::unix::ffi::OsStrExt;
@@ -18,6 +18,73 @@ use std::os::wasi::ffi::OsStrExt;
 #[cfg(feature = "std")]
 use {core::fmt, std::ffi::OsStr, std::path::Path};
 
+/// The length of the longest decimal representation of an `i128`/`u128`,
+/// including a leading `-` sign, plus a NUL terminator.
+const BUF_LEN: usize = "-170141183460469231731687303715884105728\0".len();
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Integer types that [`DecInt::new`] can format.
+///
+/// This is a sealed extension of `itoa`'s own `Integer` trait that exposes
+/// the maximum length of each type's decimal representation as an
+/// associated constant, mirroring `itoa`'s internal (but private) notion of
+/// the same thing. This lets [`DecInt::new`] bound the formatted length with
+/// a per-type constant instead of branching on `TypeId` at runtime. The
+/// stack buffer itself is not sized from this constant: every `DecInt` has
+/// the same fixed-size buffer, large enough for an `i128`/`u128`.
+pub trait MaxStrLen: Integer + sealed::Sealed {
+    /// The length of the longest string this type can format to via
+    /// [`DecInt::new`], including a leading `-` sign for signed types.
+    const MAX_LEN: usize;
+}
+
+macro_rules! impl_max_str_len {
+    ($($int:ty => $longest:expr),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $int {}
+            impl MaxStrLen for $int {
+                const MAX_LEN: usize = $longest.len();
+            }
+        )*
+    };
+}
+
+impl_max_str_len! {
+    i8 => "-128",
+    u8 => "255",
+    i16 => "-32768",
+    u16 => "65535",
+    i32 => "-2147483648",
+    u32 => "4294967295",
+    i64 => "-9223372036854775808",
+    u64 => "18446744073709551615",
+    i128 => "-170141183460469231731687303715884105728",
+    u128 => "340282366920938463463374607431768211455",
+}
+
+impl sealed::Sealed for isize {}
+impl MaxStrLen for isize {
+    const MAX_LEN: usize = match usize::BITS {
+        16 => "-32768".len(),
+        32 => "-2147483648".len(),
+        64 => "-9223372036854775808".len(),
+        _ => "-170141183460469231731687303715884105728".len(),
+    };
+}
+
+impl sealed::Sealed for usize {}
+impl MaxStrLen for usize {
+    const MAX_LEN: usize = match usize::BITS {
+        16 => "65535".len(),
+        32 => "4294967295".len(),
+        64 => "18446744073709551615".len(),
+        _ => "340282366920938463463374607431768211455".len(),
+    };
+}
+
 /// Format an integer into a decimal `Path` component, without constructing a
 /// temporary `PathBuf` or `String`.
 ///
@@ -37,48 +104,35 @@ use {core::fmt, std::ffi::OsStr, std::path::Path};
 /// ```
 #[derive(Clone)]
 pub struct DecInt {
-    // Enough to hold an i64 and NUL terminator.
-    buf: [MaybeUninit<u8>; "-9223372036854775808\0".len()],
+    // Enough to hold an i128/u128 and NUL terminator.
+    buf: [MaybeUninit<u8>; BUF_LEN],
     len: usize,
 }
 
 impl DecInt {
     /// Construct a new path component from an integer.
     #[inline]
-    pub fn new<Int: Integer + 'static>(i: Int) -> Self {
-        let mut buf = [MaybeUninit::uninit(); 21];
+    pub fn new<Int: MaxStrLen>(i: Int) -> Self {
+        let mut buf = [MaybeUninit::uninit(); BUF_LEN];
 
         let mut str_buf = Buffer::new();
         let str_buf = str_buf.format(i);
         {
-            let max_buf_size = {
-                let bits = match TypeId::of::<Int>() {
-                    id if [TypeId::of::<i8>(), TypeId::of::<u8>()].contains(&id) => u8::BITS,
-                    id if [TypeId::of::<i16>() , TypeId::of::<u16>()].contains(&id) => u16::BITS,
-                    id if [TypeId::of::<i32>() , TypeId::of::<u32>()].contains(&id) => u32::BITS,
-                    id if [TypeId::of::<i64>() , TypeId::of::<u64>()].contains(&id) => u64::BITS,
-                    id if [TypeId::of::<i128>() , TypeId::of::<u128>()].contains(&id) => u128::BITS,
-                    id if [TypeId::of::<isize>() , TypeId::of::<usize>()].contains(&id) => usize::BITS,
-                    _ => unreachable!(),
-                };
-                match bits {
-                    8 => "-128".len(),
-                    16 => "-32768".len(),
-                    32 => "-2147483648".len(),
-                    64 => "-9223372036854775808".len(),
-                    128 => "-170141183460469231731687303715884105728".len(),
-                    _ => unreachable!(),
-                }
-            };
-            if str_buf.len() > max_buf_size {
-                unsafe { core::hint::unreachable_unchecked() }
-            }
-            assert!(str_buf.len() < buf.len(), "{} unsupported.", core::any::type_name::<Int>());
+            // `Int::MAX_LEN` is the longest `str_buf` can ever be for this
+            // type, so this can never fail; it's here to document the
+            // invariant rather than to guard against it.
+            debug_assert!(str_buf.len() <= Int::MAX_LEN);
 
-            buf[..str_buf.len()].copy_from_slice(unsafe {
-                // SAFETY: you can always go from init to uninit
-                mem::transmute::<&[u8], &[MaybeUninit<u8>]>(str_buf.as_bytes())
-            });
+            // SAFETY: `str_buf.as_bytes()` and `buf` are non-overlapping,
+            // `str_buf.len() <= buf.len()` per `Int::MAX_LEN`, and writing
+            // bytes into `MaybeUninit<u8>` is always sound.
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    str_buf.as_bytes().as_ptr(),
+                    buf.as_mut_ptr().cast(),
+                    str_buf.len(),
+                );
+            }
             buf[str_buf.len()] = MaybeUninit::new(0);
         }
 
@@ -88,6 +142,69 @@ impl DecInt {
         }
     }
 
+    /// Construct a new path component from an integer, in a `const`
+    /// context.
+    ///
+    /// Unlike [`DecInt::new`], this doesn't depend on `itoa`, which isn't
+    /// `const`, so it can be used to build `const` and `static` values,
+    /// such as a table of commonly used fd numbers. It's restricted to
+    /// `i64`, which is wide enough for every integer type `DecInt::new`
+    /// accepts other than `i128`/`u64`/`u128`/`usize` values too large to
+    /// fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// use rustix::path::DecInt;
+    ///
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// const STDIN: DecInt = DecInt::new_const(0);
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// assert_eq!(STDIN.as_str(), "0");
+    ///
+    /// // `i64::MIN`'s magnitude doesn't fit in an `i64`, so this has to go
+    /// // through `unsigned_abs` rather than negation.
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// const MIN: DecInt = DecInt::new_const(i64::MIN);
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// assert_eq!(MIN.as_str(), "-9223372036854775808");
+    /// ```
+    pub const fn new_const(n: i64) -> Self {
+        // Digits accumulate least-significant-first, so build them into a
+        // scratch array and then copy them into `buf` in the right order.
+        let negative = n < 0;
+        let mut magnitude = n.unsigned_abs();
+
+        // Enough digits for any `u64` magnitude, including `i64::MIN`'s.
+        let mut digits = [0_u8; 20];
+        let mut first_digit = digits.len();
+        loop {
+            first_digit -= 1;
+            digits[first_digit] = b'0' + (magnitude % 10) as u8;
+            magnitude /= 10;
+            if magnitude == 0 {
+                break;
+            }
+        }
+
+        let mut buf = [MaybeUninit::uninit(); BUF_LEN];
+        let mut len = 0;
+        if negative {
+            buf[len] = MaybeUninit::new(b'-');
+            len += 1;
+        }
+        let mut i = first_digit;
+        while i < digits.len() {
+            buf[len] = MaybeUninit::new(digits[i]);
+            len += 1;
+            i += 1;
+        }
+        buf[len] = MaybeUninit::new(0);
+
+        Self { buf, len }
+    }
+
     /// Construct a new path component from a file descriptor.
     #[inline]
     pub fn from_fd<Fd: AsFd>(fd: Fd) -> Self {
@@ -117,8 +234,10 @@ impl DecInt {
     #[inline]
     pub fn as_bytes_with_nul(&self) -> &[u8] {
         let init = &self.buf[..=self.len];
-        // SAFETY: we're guaranteed to have initialized len+1 bytes.
-        unsafe { mem::transmute::<&[MaybeUninit<u8>], &[u8]>(init) }
+        // SAFETY: we're guaranteed to have initialized len+1 bytes, and a
+        // `*const MaybeUninit<u8>` has the same layout and provenance as a
+        // `*const u8`.
+        unsafe { core::slice::from_raw_parts(init.as_ptr().cast(), init.len()) }
     }
 
     /// Return the raw byte buffer.
@@ -0,0 +1,13 @@
+//! Path string and path component utilities.
+
+mod byte_path;
+#[cfg(any(feature = "fs", feature = "net"))]
+mod dec_int;
+#[cfg(any(feature = "fs", feature = "net"))]
+mod path_buffer;
+
+pub use byte_path::BytePath;
+#[cfg(any(feature = "fs", feature = "net"))]
+pub use dec_int::DecInt;
+#[cfg(any(feature = "fs", feature = "net"))]
+pub use path_buffer::PathBuffer;
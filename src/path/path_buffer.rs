@@ -0,0 +1,159 @@
+//! A bounded, no-alloc buffer for assembling composite paths.
+//!
+//! # Safety
+//!
+//! This uses `CStr::from_bytes_with_nul_unchecked`, `ptr::copy_nonoverlapping`,
+//! and `slice::from_raw_parts` on the buffer that it filled itself.
+#![allow(unsafe_code)]
+
+use crate::ffi::CStr;
+use core::fmt;
+use core::mem::MaybeUninit;
+
+/// A stack buffer for assembling a path (or other C string) out of several
+/// pieces, such as a constant prefix followed by a formatted integer,
+/// without allocating.
+///
+/// This is modeled on the Linux kernel's `RawFormatter`: bytes accumulate
+/// from the start of the buffer towards `N`, and the logical write
+/// position is tracked even past the end, using saturating arithmetic, so
+/// that overflow is recorded rather than silently dropped or causing
+/// undefined behavior. Use [`PathBuffer::bytes_written`] to check whether
+/// everything written actually fit before finalizing with
+/// [`PathBuffer::as_c_str`].
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(any(feature = "fs", feature = "net"))]
+/// use rustix::path::{DecInt, PathBuffer};
+/// # #[cfg(any(feature = "fs", feature = "net"))]
+/// use core::fmt::Write as _;
+///
+/// # #[cfg(any(feature = "fs", feature = "net"))]
+/// let mut buf = PathBuffer::<64>::new();
+/// # #[cfg(any(feature = "fs", feature = "net"))]
+/// write!(buf, "/proc/self/fd/{}", DecInt::new(9876).as_str()).unwrap();
+/// # #[cfg(any(feature = "fs", feature = "net"))]
+/// assert_eq!(buf.as_c_str().unwrap().to_bytes(), b"/proc/self/fd/9876");
+/// ```
+pub struct PathBuffer<const N: usize> {
+    buf: [MaybeUninit<u8>; N],
+    // The logical write position. This can exceed `N`, in which case the
+    // buffer has overflowed; it's saturated so that it never wraps.
+    pos: usize,
+}
+
+impl<const N: usize> PathBuffer<N> {
+    /// Construct a new, empty `PathBuffer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            buf: [MaybeUninit::uninit(); N],
+            pos: 0,
+        }
+    }
+
+    /// Return the number of bytes written so far, including any bytes that
+    /// didn't fit in the buffer.
+    ///
+    /// If this is greater than or equal to `N`, the buffer has overflowed
+    /// and [`PathBuffer::as_c_str`] will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// use rustix::path::PathBuffer;
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// use core::fmt::Write as _;
+    ///
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// let mut buf = PathBuffer::<8>::new();
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// write!(buf, "/proc/self").unwrap();
+    ///
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// assert!(buf.bytes_written() >= 8);
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// assert!(buf.as_c_str().is_none());
+    /// # #[cfg(any(feature = "fs", feature = "net"))]
+    /// assert_eq!(buf.as_bytes(), b"/proc/se");
+    /// ```
+    #[inline]
+    pub fn bytes_written(&self) -> usize {
+        self.pos
+    }
+
+    /// Return the bytes written so far, truncated to whatever fit in the
+    /// buffer.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let init = &self.buf[..self.pos.min(N)];
+        // SAFETY: every byte up to `self.pos.min(N)` has been initialized
+        // by `write_str`, and a `*const MaybeUninit<u8>` has the same
+        // layout and provenance as a `*const u8`.
+        unsafe { core::slice::from_raw_parts(init.as_ptr().cast(), init.len()) }
+    }
+
+    /// Finalize the buffer and return it as a `&CStr`, or `None` if the
+    /// buffer overflowed and there's no room left for a NUL terminator.
+    #[inline]
+    pub fn as_c_str(&mut self) -> Option<&CStr> {
+        if self.pos >= N {
+            return None;
+        }
+
+        self.buf[self.pos] = MaybeUninit::new(0);
+        let bytes_with_nul = &self.buf[..=self.pos];
+        // SAFETY: every byte up to and including `self.pos` has now been
+        // initialized, and a `*const MaybeUninit<u8>` has the same layout
+        // and provenance as a `*const u8`.
+        let bytes_with_nul = unsafe {
+            core::slice::from_raw_parts(bytes_with_nul.as_ptr().cast(), bytes_with_nul.len())
+        };
+        debug_assert!(CStr::from_bytes_with_nul(bytes_with_nul).is_ok());
+
+        // SAFETY: `bytes_with_nul` ends in a single NUL byte and contains
+        // no interior NULs, since `write_str` rejects any string that
+        // contains a NUL byte.
+        Some(unsafe { CStr::from_bytes_with_nul_unchecked(bytes_with_nul) })
+    }
+}
+
+impl<const N: usize> Default for PathBuffer<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for PathBuffer<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = s.as_bytes();
+        // A NUL byte would end the C string early, so refuse it.
+        if bytes.contains(&0) {
+            return Err(fmt::Error);
+        }
+        let start = self.pos.min(N);
+        let available = N - start;
+        let to_copy = available.min(bytes.len());
+
+        if to_copy > 0 {
+            // SAFETY: `bytes[..to_copy]` and `self.buf[start..start +
+            // to_copy]` are non-overlapping, `start + to_copy <= N`, and
+            // writing bytes into `MaybeUninit<u8>` is always sound.
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    bytes.as_ptr(),
+                    self.buf[start..start + to_copy].as_mut_ptr().cast(),
+                    to_copy,
+                );
+            }
+        }
+
+        self.pos = self.pos.saturating_add(bytes.len());
+        Ok(())
+    }
+}